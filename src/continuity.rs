@@ -0,0 +1,179 @@
+//! A reusable continuity-counter tracking subsystem, detecting discontinuities -- such as lost
+//! packets -- in a transport stream, per _ISO/IEC 13818-1, Section 2.4.3.3_.
+//!
+//! `Packet::continuity_counter()` and `ContinuityCounter::follows()` already exist, but nothing
+//! in this crate tracks the expected counter across packets; `ContinuityTracker` fills that gap.
+//! It is independent of any particular demultiplexing machinery -- any code which sees a parsed
+//! `Packet` can drive it -- so that all packet filter types, not only PES, can detect loss.
+//!
+//! `ContinuityTracker` is not wired into `Demultiplex`, and does not emit `Discontinuity` events
+//! to a demux context; callers drive `track()` themselves and inspect its return value.
+
+use std::collections::HashMap;
+
+use packet::{ContinuityCounter, Packet};
+
+/// A continuity-counter discontinuity detected by `ContinuityTracker::track()`: the counter
+/// carried by an incoming packet did not follow the counter last seen for the same PID.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct Discontinuity {
+    /// the PID on which the discontinuity was observed
+    pub pid: u16,
+    /// the counter value which should have followed the last one seen for this PID
+    pub expected: ContinuityCounter,
+    /// the counter value actually carried by the packet which triggered this event
+    pub received: ContinuityCounter,
+}
+
+/// Tracks, for every PID seen within a transport stream, the last `ContinuityCounter` observed on
+/// a packet carrying a payload (per `AdaptationControl::has_payload()`), reporting a
+/// `Discontinuity` whenever an incoming counter does not follow as expected.
+///
+/// Packets whose adaptation field sets `discontinuity_indicator` are allowed to restart the
+/// counter at any value -- this is how the spec signals an intentional gap, such as when
+/// switching between sources -- so `track()` simply adopts the new value rather than reporting a
+/// discontinuity for them.  A packet which repeats the last-seen counter value is a legal
+/// duplicate packet, and is likewise not reported.
+#[derive(Default)]
+pub struct ContinuityTracker {
+    by_pid: HashMap<u16, ContinuityCounter>,
+}
+
+impl ContinuityTracker {
+    /// Creates a tracker with no PIDs yet seen.
+    pub fn new() -> ContinuityTracker {
+        Default::default()
+    }
+
+    /// Checks `pk` against the state tracked so far, updating that state, and returns `Some`
+    /// discontinuity if this packet's continuity counter did not follow as expected.
+    ///
+    /// Packets for which `adaptation_control().has_payload()` is `false` are ignored entirely --
+    /// the spec does not require their continuity counter to advance -- and leave the tracked
+    /// state for their PID unchanged.
+    pub fn track<'buf>(&mut self, pk: &Packet<'buf>) -> Option<Discontinuity> {
+        if !pk.adaptation_control().has_payload() {
+            return None;
+        }
+        let pid = pk.pid();
+        let received = pk.continuity_counter();
+        let discontinuity_indicator = pk
+            .adaptation_field()
+            .is_some_and(|af| af.discontinuity_indicator());
+
+        let result = match self.by_pid.get(&pid) {
+            None => None,
+            Some(_) if discontinuity_indicator => None,
+            Some(&last) if received == last => None, // legal duplicate packet
+            Some(&last) if received.follows(last) => None,
+            Some(&last) => Some(Discontinuity {
+                pid,
+                expected: ContinuityCounter::new((last.count() + 1) & 0b1111),
+                received,
+            }),
+        };
+        self.by_pid.insert(pid, received);
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use packet::{AdaptationFieldBuilder, Packet, PacketBuilder, PACKET_SIZE};
+
+    fn packet_buf(pid: u16, cc: u8, discontinuity: bool) -> [u8; PACKET_SIZE] {
+        let payload = [0u8; 1];
+        let mut buf = [0u8; PACKET_SIZE];
+        let mut builder = PacketBuilder::new()
+            .pid(pid)
+            .continuity_counter(ContinuityCounter::new(cc))
+            .payload(&payload[..]);
+        if discontinuity {
+            builder = builder.adaptation_field(
+                AdaptationFieldBuilder::new().discontinuity_indicator(true),
+            );
+        }
+        builder.write_to(&mut buf[..]);
+        buf
+    }
+
+    #[test]
+    fn zero_length_adaptation_field_is_not_a_discontinuity() {
+        // a 183-byte payload makes `PacketBuilder` emit a zero-length adaptation field (just the
+        // length byte, no flags byte); `track()` must not panic reading its discontinuity flag.
+        let payload = [0u8; 183];
+        let mut buf = [0u8; PACKET_SIZE];
+        PacketBuilder::new()
+            .pid(0x10)
+            .continuity_counter(ContinuityCounter::new(0))
+            .payload(&payload[..])
+            .write_to(&mut buf[..]);
+
+        let mut tracker = ContinuityTracker::new();
+        assert_eq!(tracker.track(&Packet::new(&buf[..])), None);
+    }
+
+    #[test]
+    fn follows_is_not_a_discontinuity() {
+        let mut tracker = ContinuityTracker::new();
+        let first = packet_buf(0x10, 0, false);
+        assert_eq!(tracker.track(&Packet::new(&first[..])), None);
+
+        let second = packet_buf(0x10, 1, false);
+        assert_eq!(tracker.track(&Packet::new(&second[..])), None);
+    }
+
+    #[test]
+    fn duplicate_packet_is_not_a_discontinuity() {
+        let mut tracker = ContinuityTracker::new();
+        let first = packet_buf(0x10, 3, false);
+        tracker.track(&Packet::new(&first[..]));
+
+        let duplicate = packet_buf(0x10, 3, false);
+        assert_eq!(tracker.track(&Packet::new(&duplicate[..])), None);
+    }
+
+    #[test]
+    fn gap_is_reported() {
+        let mut tracker = ContinuityTracker::new();
+        let first = packet_buf(0x10, 1, false);
+        tracker.track(&Packet::new(&first[..]));
+
+        let gapped = packet_buf(0x10, 5, false);
+        assert_eq!(
+            tracker.track(&Packet::new(&gapped[..])),
+            Some(Discontinuity {
+                pid: 0x10,
+                expected: ContinuityCounter::new(2),
+                received: ContinuityCounter::new(5),
+            })
+        );
+    }
+
+    #[test]
+    fn discontinuity_indicator_resets_expectation() {
+        let mut tracker = ContinuityTracker::new();
+        let first = packet_buf(0x10, 1, false);
+        tracker.track(&Packet::new(&first[..]));
+
+        let restarted = packet_buf(0x10, 9, true);
+        assert_eq!(tracker.track(&Packet::new(&restarted[..])), None);
+
+        // tracking resumes normally from the new value
+        let next = packet_buf(0x10, 10, false);
+        assert_eq!(tracker.track(&Packet::new(&next[..])), None);
+    }
+
+    #[test]
+    fn distinct_pids_are_tracked_independently() {
+        let mut tracker = ContinuityTracker::new();
+        let pid_a = packet_buf(0x10, 0, false);
+        let pid_b = packet_buf(0x20, 7, false);
+        tracker.track(&Packet::new(&pid_a[..]));
+        tracker.track(&Packet::new(&pid_b[..]));
+
+        let pid_a_next = packet_buf(0x10, 1, false);
+        assert_eq!(tracker.track(&Packet::new(&pid_a_next[..])), None);
+    }
+}
@@ -0,0 +1,94 @@
+//! Tallies simple stream-health counters -- `transport_error_indicator` occurrences and per-PID
+//! continuity-counter breaks -- so that applications can report on stream health instead of each
+//! problem being silently dropped, as `fq`'s decoder does.
+//!
+//! Like `ContinuityTracker`, which this builds on, `StreamHealth` is independent of any
+//! particular demultiplexing machinery: any code which sees a parsed `Packet` can drive it. It is
+//! not wired into `Demultiplex`, and does not push counters to a demux context as they change;
+//! `StreamHealth` only accumulates totals for callers to read back.
+
+use continuity::ContinuityTracker;
+use packet::Packet;
+
+/// Running totals describing the health of a transport stream, updated packet by packet via
+/// `track()`.
+#[derive(Default)]
+pub struct StreamHealth {
+    continuity: ContinuityTracker,
+    transport_error_indicator_count: u64,
+    continuity_discontinuity_count: u64,
+}
+
+impl StreamHealth {
+    /// Creates a tracker with all counters at zero.
+    pub fn new() -> StreamHealth {
+        Default::default()
+    }
+
+    /// Updates the running totals for `pk`: incrementing the transport-error-indicator count if
+    /// it is set, and the continuity-discontinuity count if `pk` represents a break in the
+    /// expected continuity counter for its PID (per `ContinuityTracker::track()`).
+    pub fn track<'buf>(&mut self, pk: &Packet<'buf>) {
+        if pk.transport_error_indicator() {
+            self.transport_error_indicator_count += 1;
+        }
+        if self.continuity.track(pk).is_some() {
+            self.continuity_discontinuity_count += 1;
+        }
+    }
+
+    /// The number of packets seen so far with `transport_error_indicator` set.
+    pub fn transport_error_indicator_count(&self) -> u64 {
+        self.transport_error_indicator_count
+    }
+
+    /// The number of continuity-counter discontinuities detected so far, across all PIDs.
+    pub fn continuity_discontinuity_count(&self) -> u64 {
+        self.continuity_discontinuity_count
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use packet::{ContinuityCounter, Packet, PacketBuilder, PACKET_SIZE};
+
+    fn packet_buf(pid: u16, cc: u8, tei: bool) -> [u8; PACKET_SIZE] {
+        let payload = [0u8; 1];
+        let mut buf = [0u8; PACKET_SIZE];
+        PacketBuilder::new()
+            .pid(pid)
+            .transport_error_indicator(tei)
+            .continuity_counter(ContinuityCounter::new(cc))
+            .payload(&payload[..])
+            .write_to(&mut buf[..]);
+        buf
+    }
+
+    #[test]
+    fn counts_start_at_zero() {
+        let health = StreamHealth::new();
+        assert_eq!(health.transport_error_indicator_count(), 0);
+        assert_eq!(health.continuity_discontinuity_count(), 0);
+    }
+
+    #[test]
+    fn tallies_transport_error_indicator() {
+        let mut health = StreamHealth::new();
+        let ok = packet_buf(0x10, 0, false);
+        health.track(&Packet::new(&ok[..]));
+        let bad = packet_buf(0x10, 1, true);
+        health.track(&Packet::new(&bad[..]));
+        assert_eq!(health.transport_error_indicator_count(), 1);
+    }
+
+    #[test]
+    fn tallies_continuity_discontinuities() {
+        let mut health = StreamHealth::new();
+        let first = packet_buf(0x10, 0, false);
+        health.track(&Packet::new(&first[..]));
+        let gapped = packet_buf(0x10, 5, false);
+        health.track(&Packet::new(&gapped[..]));
+        assert_eq!(health.continuity_discontinuity_count(), 1);
+    }
+}
@@ -57,8 +57,54 @@ impl TransportScramblingControl {
     }
 }
 
+/// The Program Clock Reference is a measure of time, in units such that there are 27,000,000 of
+/// them per second, used to allow a decoder to synchronise its own clock with the encoder's.
+///
+/// See _ISO/IEC 13818-1, Section 2.4.2.2_.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct ClockRef {
+    base: u64,
+    extension: u16,
+}
+
+impl ClockRef {
+    /// `base` must fit within 33 bits, and `extension` within 9 bits.
+    #[inline]
+    pub fn new(base: u64, extension: u16) -> ClockRef {
+        assert!(base < 1 << 33);
+        assert!(extension < 1 << 9);
+        ClockRef { base, extension }
+    }
+
+    /// The base component of this clock reference, a 33-bit value counting at 90kHz.
+    #[inline]
+    pub fn base(&self) -> u64 {
+        self.base
+    }
+
+    /// The extension component of this clock reference, a 9-bit value counting at 27MHz, used to
+    /// extend the precision of `base()`.
+    #[inline]
+    pub fn extension(&self) -> u16 {
+        self.extension
+    }
+
+    /// The full clock value, expressed as a count of 27MHz clock periods, per
+    /// _ISO/IEC 13818-1, Section 2.4.2.2_: `base * 300 + extension`.
+    #[inline]
+    pub fn full_value(&self) -> u64 {
+        self.base * 300 + u64::from(self.extension)
+    }
+}
+
 /// A collection of fields that may optionally appear within the header of a transport stream
 /// `Packet`.
+///
+/// Which fields are actually present is indicated by a set of flag bits in the first byte of the
+/// underlying buffer; accessors for fields which the flags indicate are absent will return
+/// `None`, as will any accessor for which the underlying buffer is too short to hold the field
+/// (so that malformed data cannot cause a panic).  `discontinuity_indicator()` returns a plain
+/// `bool`, and reads as `false` when the buffer is too short to hold the flags byte.
 pub struct AdaptationField<'buf> {
     buf: &'buf [u8],
 }
@@ -68,8 +114,145 @@ impl<'buf> AdaptationField<'buf> {
         AdaptationField { buf }
     }
 
+    fn flags(&self) -> Option<u8> {
+        self.buf.first().copied()
+    }
+
     pub fn discontinuity_indicator(&self) -> bool {
-        self.buf[0] & 0b10000000 != 0
+        self.flags().is_some_and(|f| f & 0b10000000 != 0)
+    }
+
+    /// Indicates that the current transport stream packet, and possibly subsequent packets with
+    /// the same PID, contain some information to aid random access at this point.
+    pub fn random_access_indicator(&self) -> Option<bool> {
+        self.flags().map(|f| f & 0b01000000 != 0)
+    }
+
+    /// Indicates the priority of the elementary stream data carried within this packet's payload,
+    /// relative to other packets with the same PID.
+    pub fn elementary_stream_priority_indicator(&self) -> Option<bool> {
+        self.flags().map(|f| f & 0b00100000 != 0)
+    }
+
+    fn pcr_flag(&self) -> Option<bool> {
+        self.flags().map(|f| f & 0b00010000 != 0)
+    }
+
+    fn opcr_flag(&self) -> Option<bool> {
+        self.flags().map(|f| f & 0b00001000 != 0)
+    }
+
+    fn splicing_point_flag(&self) -> Option<bool> {
+        self.flags().map(|f| f & 0b00000100 != 0)
+    }
+
+    fn transport_private_data_flag(&self) -> Option<bool> {
+        self.flags().map(|f| f & 0b00000010 != 0)
+    }
+
+    fn adaptation_field_extension_flag(&self) -> Option<bool> {
+        self.flags().map(|f| f & 0b00000001 != 0)
+    }
+
+    // Parses the 6-byte PCR/OPCR representation found at the given offset (relative to the start
+    // of the flags byte), per _ISO/IEC 13818-1, Section 2.4.2.2_: a 33-bit base, 6 reserved bits,
+    // then a 9-bit extension.
+    fn clock_ref_at(&self, offset: usize) -> Option<ClockRef> {
+        let b = self.buf.get(offset..offset + 6)?;
+        let base = u64::from(b[0]) << 25
+            | u64::from(b[1]) << 17
+            | u64::from(b[2]) << 9
+            | u64::from(b[3]) << 1
+            | u64::from(b[4]) >> 7;
+        let extension = u16::from(b[4] & 0b00000001) << 8 | u16::from(b[5]);
+        Some(ClockRef::new(base, extension))
+    }
+
+    /// The Program Clock Reference value carried by this adaptation field, if `pcr_flag` is set
+    /// and the buffer is long enough to hold it.
+    pub fn pcr(&self) -> Option<ClockRef> {
+        if self.pcr_flag()? {
+            self.clock_ref_at(1)
+        } else {
+            None
+        }
+    }
+
+    fn opcr_offset(&self) -> usize {
+        1 + if self.pcr_flag().unwrap_or(false) { 6 } else { 0 }
+    }
+
+    /// The Original Program Clock Reference value carried by this adaptation field, if
+    /// `opcr_flag` is set and the buffer is long enough to hold it.
+    ///
+    /// This field is used by programs which have been re-multiplexed from another transport
+    /// stream, to allow the original PCR values to be restored.
+    pub fn opcr(&self) -> Option<ClockRef> {
+        if self.opcr_flag()? {
+            self.clock_ref_at(self.opcr_offset())
+        } else {
+            None
+        }
+    }
+
+    fn splice_countdown_offset(&self) -> usize {
+        self.opcr_offset() + if self.opcr_flag().unwrap_or(false) { 6 } else { 0 }
+    }
+
+    /// A count of the number of packets remaining, with the same PID, before a splicing point is
+    /// reached, if `splicing_point_flag` is set.  A negative value indicates that a splice point
+    /// occurred before this packet, and the absolute value is the time since the splice point,
+    /// in packets.
+    pub fn splice_countdown(&self) -> Option<i8> {
+        if self.splicing_point_flag()? {
+            self.buf.get(self.splice_countdown_offset()).map(|&b| b as i8)
+        } else {
+            None
+        }
+    }
+
+    fn private_data_offset(&self) -> usize {
+        self.splice_countdown_offset() + if self.splicing_point_flag().unwrap_or(false) { 1 } else { 0 }
+    }
+
+    /// Private data bytes carried within this adaptation field, if `transport_private_data_flag`
+    /// is set, not otherwise interpreted by this crate.
+    pub fn transport_private_data(&self) -> Option<&'buf [u8]> {
+        if self.transport_private_data_flag()? {
+            let offset = self.private_data_offset();
+            let len = *self.buf.get(offset)? as usize;
+            self.buf.get(offset + 1..offset + 1 + len)
+        } else {
+            None
+        }
+    }
+
+    fn private_data_len(&self) -> usize {
+        if self.transport_private_data_flag().unwrap_or(false) {
+            self.buf
+                .get(self.private_data_offset())
+                .map(|&len| 1 + len as usize)
+                .unwrap_or(0)
+        } else {
+            0
+        }
+    }
+
+    fn extension_offset(&self) -> usize {
+        self.private_data_offset() + self.private_data_len()
+    }
+
+    /// The contents of the `adaptation_field_extension`, if `adaptation_field_extension_flag` is
+    /// set.  This crate does not interpret the contents of the extension, simply returning the
+    /// raw bytes (excluding the length field itself).
+    pub fn adaptation_field_extension(&self) -> Option<&'buf [u8]> {
+        if self.adaptation_field_extension_flag()? {
+            let offset = self.extension_offset();
+            let len = *self.buf.get(offset)? as usize;
+            self.buf.get(offset + 1..offset + 1 + len)
+        } else {
+            None
+        }
     }
 }
 
@@ -119,6 +302,29 @@ impl ContinuityCounter {
     }
 }
 
+/// Problems that can occur while interpreting the bytes of a `Packet`, reported by the
+/// `_checked()` variants of `Packet::adaptation_field()` and `Packet::payload()`.  Fuzzing has
+/// shown transport streams in the wild contain packets like these; rather than printing a
+/// message and dropping the data, calling code can inspect the specific problem.
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+pub enum PacketError {
+    /// `adaptation_field_length` did not equal the fixed value required when
+    /// `adaptation_control` is `AdaptationFieldOnly`
+    BadAdaptationFieldOnlyLength { len: usize },
+    /// `adaptation_field_length` exceeded the 182 bytes which can appear alongside a payload,
+    /// when `adaptation_control` is `AdaptationFieldAndPayload`
+    BadAdaptationFieldAndPayloadLength { len: usize },
+    /// `adaptation_control` indicated a payload should be present, but `adaptation_field_length`
+    /// left no bytes of the packet available to hold it
+    NoPayload,
+    /// `adaptation_field_length` was large enough that the indicated payload offset falls beyond
+    /// the end of the packet buffer
+    AdaptationFieldLengthTooLarge { len: usize },
+    /// `adaptation_control` held the reserved value `0b00`, which the spec does not define a
+    /// meaning for
+    ReservedAdaptationControl,
+}
+
 /// A transport stream `Packet` is a wrapper around a byte slice which allows the bytes to be
 /// interpreted as a packet structure per _ISO/IEC 13818-1, Section 2.4.3.3_.
 pub struct Packet<'buf> {
@@ -202,32 +408,32 @@ impl<'buf> Packet<'buf> {
     }
 
     /// An `AdaptationField` contains additional packet headers that may be present in the packet.
+    ///
+    /// Returns `None` both when `adaptation_control()` indicates no adaptation field is present,
+    /// and when one is present but malformed; use `adaptation_field_checked()` to distinguish
+    /// those cases.
     pub fn adaptation_field(&self) -> Option<AdaptationField> {
+        self.adaptation_field_checked().unwrap_or(None)
+    }
+
+    /// As `adaptation_field()`, but surfaces why no `AdaptationField` could be produced, rather
+    /// than silently returning `None`.
+    pub fn adaptation_field_checked(&self) -> Result<Option<AdaptationField>, PacketError> {
         match self.adaptation_control() {
-            AdaptationControl::Reserved | AdaptationControl::PayloadOnly => None,
+            AdaptationControl::Reserved | AdaptationControl::PayloadOnly => Ok(None),
             AdaptationControl::AdaptationFieldOnly => {
                 let len = self.adaptation_field_length();
                 if len != (PACKET_SIZE - ADAPTATION_FIELD_OFFSET) {
-                    println!(
-                        "invalid adaptation_field_length for AdaptationFieldOnly: {}",
-                        len
-                    );
-                    // TODO: Option<Result<AdaptationField>> instead?
-                    return None;
+                    return Err(PacketError::BadAdaptationFieldOnlyLength { len });
                 }
-                Some(self.mk_af(len))
+                Ok(Some(self.mk_af(len)))
             }
             AdaptationControl::AdaptationFieldAndPayload => {
                 let len = self.adaptation_field_length();
                 if len > 182 {
-                    println!(
-                        "invalid adaptation_field_length for AdaptationFieldAndPayload: {}",
-                        len
-                    );
-                    // TODO: Option<Result<AdaptationField>> instead?
-                    return None;
+                    return Err(PacketError::BadAdaptationFieldAndPayloadLength { len });
                 }
-                Some(self.mk_af(len))
+                Ok(Some(self.mk_af(len)))
             }
         }
     }
@@ -240,27 +446,37 @@ impl<'buf> Packet<'buf> {
 
     /// The data contained within the packet, not including the packet headers.
     /// Not all packets have a payload, and `None` is returned if `adaptation_control()` indicates
-    /// that no payload is present.  None may also be returned if the packet is malformed.
+    /// that no payload is present.  None may also be returned if the packet is malformed; use
+    /// `payload_checked()` to distinguish those cases.
     /// If `Some` payload is returned, it is guaranteed not to be an empty slice.
     #[inline(always)]
     pub fn payload(&self) -> Option<&'buf [u8]> {
+        self.payload_checked().unwrap_or(None)
+    }
+
+    /// As `payload()`, but surfaces why no payload could be produced, rather than silently
+    /// returning `None`.
+    pub fn payload_checked(&self) -> Result<Option<&'buf [u8]>, PacketError> {
         match self.adaptation_control() {
-            AdaptationControl::Reserved | AdaptationControl::AdaptationFieldOnly => None,
-            AdaptationControl::PayloadOnly | AdaptationControl::AdaptationFieldAndPayload => self.mk_payload(),
+            AdaptationControl::Reserved => Err(PacketError::ReservedAdaptationControl),
+            AdaptationControl::AdaptationFieldOnly => Ok(None),
+            AdaptationControl::PayloadOnly | AdaptationControl::AdaptationFieldAndPayload => {
+                self.mk_payload().map(Some)
+            }
         }
     }
 
     #[inline]
-    fn mk_payload(&self) -> Option<&'buf [u8]> {
+    fn mk_payload(&self) -> Result<&'buf [u8], PacketError> {
         let offset = self.content_offset();
         if offset == self.buf.len() {
-            println!("no payload data present");
-            None
+            Err(PacketError::NoPayload)
         } else if offset > self.buf.len() {
-            println!("adaptation_field_length {} too large", self.adaptation_field_length());
-            None
+            Err(PacketError::AdaptationFieldLengthTooLarge {
+                len: self.adaptation_field_length(),
+            })
         } else {
-            Some(&self.buf[offset..])
+            Ok(&self.buf[offset..])
         }
     }
 
@@ -286,6 +502,331 @@ pub trait PacketConsumer<Ret> {
     fn consume(&mut self, pk: Packet) -> Option<Ret>;
 }
 
+fn write_clock_ref(buf: &mut [u8], cr: ClockRef) {
+    let base = cr.base();
+    let ext = cr.extension();
+    buf[0] = (base >> 25) as u8;
+    buf[1] = (base >> 17) as u8;
+    buf[2] = (base >> 9) as u8;
+    buf[3] = (base >> 1) as u8;
+    buf[4] = (((base & 1) as u8) << 7) | 0b0111_1110 | ((ext >> 8) as u8 & 0b1);
+    buf[5] = ext as u8;
+}
+
+/// Assembles the contents of an [`AdaptationField`](struct.AdaptationField.html), ready to be
+/// written into a transport stream packet by [`PacketBuilder`](struct.PacketBuilder.html).
+///
+/// Any stuffing bytes needed to pad the adaptation field out to the space available within the
+/// enclosing packet are added automatically by `PacketBuilder`, rather than by this builder.
+#[derive(Default, Clone, Copy)]
+pub struct AdaptationFieldBuilder<'a> {
+    discontinuity_indicator: bool,
+    random_access_indicator: bool,
+    elementary_stream_priority_indicator: bool,
+    pcr: Option<ClockRef>,
+    opcr: Option<ClockRef>,
+    splice_countdown: Option<i8>,
+    transport_private_data: Option<&'a [u8]>,
+    adaptation_field_extension: Option<&'a [u8]>,
+}
+
+impl<'a> AdaptationFieldBuilder<'a> {
+    /// Creates a builder for an adaptation field with none of the optional fields set.
+    pub fn new() -> AdaptationFieldBuilder<'a> {
+        Default::default()
+    }
+
+    pub fn discontinuity_indicator(mut self, val: bool) -> Self {
+        self.discontinuity_indicator = val;
+        self
+    }
+
+    pub fn random_access_indicator(mut self, val: bool) -> Self {
+        self.random_access_indicator = val;
+        self
+    }
+
+    pub fn elementary_stream_priority_indicator(mut self, val: bool) -> Self {
+        self.elementary_stream_priority_indicator = val;
+        self
+    }
+
+    pub fn pcr(mut self, pcr: ClockRef) -> Self {
+        self.pcr = Some(pcr);
+        self
+    }
+
+    pub fn opcr(mut self, opcr: ClockRef) -> Self {
+        self.opcr = Some(opcr);
+        self
+    }
+
+    pub fn splice_countdown(mut self, val: i8) -> Self {
+        self.splice_countdown = Some(val);
+        self
+    }
+
+    pub fn transport_private_data(mut self, data: &'a [u8]) -> Self {
+        self.transport_private_data = Some(data);
+        self
+    }
+
+    pub fn adaptation_field_extension(mut self, data: &'a [u8]) -> Self {
+        self.adaptation_field_extension = Some(data);
+        self
+    }
+
+    /// The number of bytes this adaptation field will occupy once serialized by `write_to()`,
+    /// excluding the `adaptation_field_length` byte itself and any stuffing.
+    pub fn minimum_len(&self) -> usize {
+        1 // flags byte
+            + self.pcr.map_or(0, |_| 6)
+            + self.opcr.map_or(0, |_| 6)
+            + self.splice_countdown.map_or(0, |_| 1)
+            + self.transport_private_data.map_or(0, |d| 1 + d.len())
+            + self.adaptation_field_extension.map_or(0, |d| 1 + d.len())
+    }
+
+    /// Serializes this adaptation field's content into `buf`, which must be exactly
+    /// `self.minimum_len()` bytes long.  Does not write any stuffing bytes.
+    pub fn write_to(&self, buf: &mut [u8]) {
+        assert_eq!(buf.len(), self.minimum_len());
+        let mut flags = 0u8;
+        if self.discontinuity_indicator {
+            flags |= 0b1000_0000;
+        }
+        if self.random_access_indicator {
+            flags |= 0b0100_0000;
+        }
+        if self.elementary_stream_priority_indicator {
+            flags |= 0b0010_0000;
+        }
+        if self.pcr.is_some() {
+            flags |= 0b0001_0000;
+        }
+        if self.opcr.is_some() {
+            flags |= 0b0000_1000;
+        }
+        if self.splice_countdown.is_some() {
+            flags |= 0b0000_0100;
+        }
+        if self.transport_private_data.is_some() {
+            flags |= 0b0000_0010;
+        }
+        if self.adaptation_field_extension.is_some() {
+            flags |= 0b0000_0001;
+        }
+        buf[0] = flags;
+        let mut pos = 1;
+        if let Some(pcr) = self.pcr {
+            write_clock_ref(&mut buf[pos..pos + 6], pcr);
+            pos += 6;
+        }
+        if let Some(opcr) = self.opcr {
+            write_clock_ref(&mut buf[pos..pos + 6], opcr);
+            pos += 6;
+        }
+        if let Some(sc) = self.splice_countdown {
+            buf[pos] = sc as u8;
+            pos += 1;
+        }
+        if let Some(data) = self.transport_private_data {
+            buf[pos] = data.len() as u8;
+            pos += 1;
+            buf[pos..pos + data.len()].copy_from_slice(data);
+            pos += data.len();
+        }
+        if let Some(data) = self.adaptation_field_extension {
+            buf[pos] = data.len() as u8;
+            pos += 1;
+            buf[pos..pos + data.len()].copy_from_slice(data);
+            pos += data.len();
+        }
+        debug_assert_eq!(pos, buf.len());
+    }
+}
+
+/// Assembles the 188 bytes of a single transport stream `Packet`, as an alternative to parsing a
+/// `Packet` out of an existing buffer -- the `Packet` and `AdaptationField` types are otherwise
+/// pure read-only views over borrowed bytes, with no way to produce them.  Useful for remuxing,
+/// and for letting tests construct packets without hand-poking byte arrays.
+#[derive(Default, Clone)]
+pub struct PacketBuilder<'a> {
+    transport_error_indicator: bool,
+    payload_unit_start_indicator: bool,
+    transport_priority: bool,
+    pid: u16,
+    transport_scrambling_control: u8,
+    continuity_counter: Option<ContinuityCounter>,
+    adaptation_field: Option<AdaptationFieldBuilder<'a>>,
+    payload: Option<&'a [u8]>,
+}
+
+impl<'a> PacketBuilder<'a> {
+    /// Creates a builder for a packet with none of its fields set.
+    pub fn new() -> PacketBuilder<'a> {
+        Default::default()
+    }
+
+    pub fn transport_error_indicator(mut self, val: bool) -> Self {
+        self.transport_error_indicator = val;
+        self
+    }
+
+    pub fn payload_unit_start_indicator(mut self, val: bool) -> Self {
+        self.payload_unit_start_indicator = val;
+        self
+    }
+
+    pub fn transport_priority(mut self, val: bool) -> Self {
+        self.transport_priority = val;
+        self
+    }
+
+    /// Panics if `pid` does not fit within 13 bits.
+    pub fn pid(mut self, pid: u16) -> Self {
+        assert!(pid < 0b0010_0000_0000_0000);
+        self.pid = pid;
+        self
+    }
+
+    pub fn transport_scrambling_control(mut self, val: TransportScramblingControl) -> Self {
+        self.transport_scrambling_control = match val {
+            TransportScramblingControl::NotScrambled => 0,
+            TransportScramblingControl::Undefined1 => 1,
+            TransportScramblingControl::Undefined2 => 2,
+            TransportScramblingControl::Undefined3 => 3,
+        };
+        self
+    }
+
+    pub fn continuity_counter(mut self, val: ContinuityCounter) -> Self {
+        self.continuity_counter = Some(val);
+        self
+    }
+
+    pub fn adaptation_field(mut self, val: AdaptationFieldBuilder<'a>) -> Self {
+        self.adaptation_field = Some(val);
+        self
+    }
+
+    pub fn payload(mut self, val: &'a [u8]) -> Self {
+        self.payload = Some(val);
+        self
+    }
+
+    /// Serializes this packet into `buf`, which must be exactly `PACKET_SIZE` bytes long.  Any
+    /// space left over between the header fields set and `PACKET_SIZE` is filled with `0xFF`
+    /// stuffing bytes, as part of an adaptation field -- since, per the spec, a `PayloadOnly`
+    /// packet has nowhere else to put them, a short payload given without an adaptation field of
+    /// its own causes a minimal one to be synthesized purely to carry the stuffing.
+    ///
+    /// Panics if the configured adaptation field and payload are together too large to fit in a
+    /// single packet.
+    pub fn write_to(&self, buf: &mut [u8]) {
+        assert_eq!(buf.len(), PACKET_SIZE);
+        buf[0] = SYNC_BYTE;
+        buf[1] = (if self.transport_error_indicator { 0b1000_0000 } else { 0 })
+            | (if self.payload_unit_start_indicator { 0b0100_0000 } else { 0 })
+            | (if self.transport_priority { 0b0010_0000 } else { 0 })
+            | ((self.pid >> 8) as u8 & 0b0001_1111);
+        buf[2] = self.pid as u8;
+
+        let payload_len = self.payload.map_or(0, <[u8]>::len);
+        assert!(
+            payload_len <= PACKET_SIZE - FIXED_HEADER_SIZE,
+            "payload does not fit in a single packet"
+        );
+        let available_without_af = PACKET_SIZE - FIXED_HEADER_SIZE;
+        let available_with_af = PACKET_SIZE - ADAPTATION_FIELD_OFFSET;
+        let needs_stuffing = payload_len < available_without_af;
+        // A payload of exactly `available_with_af` bytes needs an adaptation field that
+        // contributes no content of its own -- just its one-byte `adaptation_field_length`,
+        // itself set to zero, per _ISO/IEC 13818-1, Section 2.4.3.5_ -- rather than the smallest
+        // `AdaptationFieldBuilder`, whose mandatory flags byte would be one byte too many to
+        // leave room for the payload.
+        let zero_length_af =
+            self.adaptation_field.is_none() && needs_stuffing && payload_len == available_with_af;
+        let synthesized_af = if self.adaptation_field.is_none() && needs_stuffing && !zero_length_af
+        {
+            Some(AdaptationFieldBuilder::new())
+        } else {
+            None
+        };
+        let af = self.adaptation_field.as_ref().or(synthesized_af.as_ref());
+        let has_af = af.is_some() || zero_length_af;
+
+        let adaptation_control: u8 = match (has_af, self.payload.is_some()) {
+            (false, false) => 0,
+            (false, true) => 1,
+            (true, false) => 2,
+            (true, true) => 3,
+        };
+        let cc = self.continuity_counter.map_or(0, |cc| cc.count());
+        buf[3] = (self.transport_scrambling_control << 6) | (adaptation_control << 4) | (cc & 0b0000_1111);
+
+        match (af, self.payload) {
+            _ if zero_length_af => {
+                buf[FIXED_HEADER_SIZE] = 0;
+                buf[ADAPTATION_FIELD_OFFSET..].copy_from_slice(self.payload.unwrap());
+            }
+            (None, None) => {
+                for b in &mut buf[FIXED_HEADER_SIZE..] {
+                    *b = 0xFF;
+                }
+            }
+            (None, Some(payload)) => {
+                // `needs_stuffing` is false here, so `payload` fills the packet exactly.
+                buf[FIXED_HEADER_SIZE..].copy_from_slice(payload);
+            }
+            (Some(af), payload) => {
+                let min_af_len = af.minimum_len();
+                assert!(
+                    min_af_len + payload_len <= available_with_af,
+                    "adaptation field and payload do not fit in a single packet"
+                );
+                let af_len = available_with_af - payload_len;
+                buf[FIXED_HEADER_SIZE] = af_len as u8;
+                af.write_to(&mut buf[ADAPTATION_FIELD_OFFSET..ADAPTATION_FIELD_OFFSET + min_af_len]);
+                for b in &mut buf[ADAPTATION_FIELD_OFFSET + min_af_len..ADAPTATION_FIELD_OFFSET + af_len] {
+                    *b = 0xFF;
+                }
+                if let Some(payload) = payload {
+                    buf[ADAPTATION_FIELD_OFFSET + af_len..].copy_from_slice(payload);
+                }
+            }
+        }
+    }
+}
+
+/// The default number of consecutive `PACKET_SIZE`-spaced sync bytes that `find_resync()` requires
+/// before it will trust that a candidate offset really is packet-aligned, rather than a `0x47`
+/// byte occurring coincidentally within payload data.
+pub const DEFAULT_RESYNC_CONFIRM_COUNT: usize = 3;
+
+/// Scans `buf`, looking for a byte offset at which [`SYNC_BYTE`](constant.SYNC_BYTE.html) recurs
+/// at [`PACKET_SIZE`](constant.PACKET_SIZE.html)-byte spacing, so that a demultiplexer which has
+/// lost synchronisation -- for example because a byte was dropped or inserted somewhere earlier
+/// in the transport stream -- can recover and resume processing.
+///
+/// A candidate offset is only accepted once `confirm_count` sync bytes in a row are found at the
+/// expected spacing starting from it, so that a coincidental `0x47` value within packet payload
+/// data does not cause a false resynchronisation.  The search gives up, returning `None`, once
+/// `max_sync_seek` bytes of `buf` have been examined without finding a position meeting that
+/// bar.
+///
+/// This is a standalone scanner: it does not itself resume reading from the offset it finds, or
+/// report how many bytes were skipped to get there, and it is not wired into `Demultiplex` --
+/// callers apply the returned offset themselves.
+pub fn find_resync(buf: &[u8], max_sync_seek: usize, confirm_count: usize) -> Option<usize> {
+    assert!(confirm_count >= 1);
+    let limit = buf.len().min(max_sync_seek);
+    (0..limit).find(|&start| {
+        buf[start] == SYNC_BYTE
+            && (0..confirm_count).all(|i| buf.get(start + i * PACKET_SIZE) == Some(&SYNC_BYTE))
+    })
+}
+
 #[cfg(test)]
 mod test {
     use packet::*;
@@ -319,4 +860,205 @@ mod test {
         assert!(pk.adaptation_field().is_some());
         assert!(pk.adaptation_field().unwrap().discontinuity_indicator());
     }
+
+    #[test]
+    fn adaptation_field_pcr() {
+        let mut buf = [0u8; self::PACKET_SIZE];
+        buf[0] = self::SYNC_BYTE;
+        buf[3] = 0b0010_0000; // adaptation field only
+        buf[4] = PACKET_SIZE as u8 - ADAPTATION_FIELD_OFFSET as u8;
+        buf[5] = 0b0001_0000; // pcr_flag
+        // base=1, extension=0 -> full_value() == 300
+        buf[6] = 0b0000_0000;
+        buf[7] = 0b0000_0000;
+        buf[8] = 0b0000_0000;
+        buf[9] = 0b0000_0000;
+        buf[10] = 0b1000_0000;
+        buf[11] = 0b0000_0000;
+        let pk = Packet::new(&buf[..]);
+        let af = pk.adaptation_field().unwrap();
+        let pcr = af.pcr().unwrap();
+        assert_eq!(pcr.base(), 1);
+        assert_eq!(pcr.extension(), 0);
+        assert_eq!(pcr.full_value(), 300);
+        assert!(af.opcr().is_none());
+        assert!(af.splice_countdown().is_none());
+        assert!(af.transport_private_data().is_none());
+        assert!(af.adaptation_field_extension().is_none());
+    }
+
+    #[test]
+    fn resync_finds_realignment() {
+        let mut buf = [0u8; PACKET_SIZE * 3];
+        // corrupt the very first byte, then lay down three well-aligned packets from offset 1
+        buf[0] = 0x00;
+        buf[1] = self::SYNC_BYTE;
+        buf[1 + PACKET_SIZE] = self::SYNC_BYTE;
+        buf[1 + 2 * PACKET_SIZE] = self::SYNC_BYTE;
+        assert_eq!(find_resync(&buf[..], 10, 3), Some(1));
+    }
+
+    #[test]
+    fn resync_ignores_coincidental_sync_byte() {
+        let mut buf = [0u8; PACKET_SIZE * 3];
+        // a stray 0x47 in payload data which is not actually packet-aligned
+        buf[5] = self::SYNC_BYTE;
+        assert_eq!(find_resync(&buf[..], 10, 3), None);
+    }
+
+    #[test]
+    fn resync_respects_max_sync_seek() {
+        let mut buf = [0u8; PACKET_SIZE * 2];
+        buf[50] = self::SYNC_BYTE;
+        buf[50 + PACKET_SIZE] = self::SYNC_BYTE;
+        assert_eq!(find_resync(&buf[..], 10, 2), None);
+        assert_eq!(find_resync(&buf[..], 60, 2), Some(50));
+    }
+
+    #[test]
+    fn adaptation_field_checked_reports_bad_length() {
+        let mut buf = [0u8; self::PACKET_SIZE];
+        buf[0] = self::SYNC_BYTE;
+        buf[3] = 0b0010_0000; // AdaptationFieldOnly
+        buf[4] = 1; // wrong: must equal PACKET_SIZE - ADAPTATION_FIELD_OFFSET
+        let pk = Packet::new(&buf[..]);
+        match pk.adaptation_field_checked() {
+            Err(PacketError::BadAdaptationFieldOnlyLength { len: 1 }) => (),
+            other => panic!("unexpected result: {}", other.is_ok()),
+        }
+        assert!(pk.adaptation_field().is_none());
+    }
+
+    #[test]
+    fn payload_checked_reports_reserved_adaptation_control() {
+        let mut buf = [0u8; self::PACKET_SIZE];
+        buf[0] = self::SYNC_BYTE; // adaptation_control left at 0b00 (Reserved)
+        let pk = Packet::new(&buf[..]);
+        assert_eq!(
+            pk.payload_checked(),
+            Err(PacketError::ReservedAdaptationControl)
+        );
+        assert!(pk.payload().is_none());
+    }
+
+    #[test]
+    fn build_payload_only_packet() {
+        // a `PayloadOnly` packet has no adaptation field to hold stuffing, so (per
+        // `PacketBuilder::write_to()`) the payload must fill the packet exactly.
+        let payload = [0xabu8; PACKET_SIZE - 4];
+        let mut buf = [0u8; PACKET_SIZE];
+        PacketBuilder::new()
+            .pid(0x123)
+            .continuity_counter(ContinuityCounter::new(7))
+            .payload_unit_start_indicator(true)
+            .payload(&payload[..])
+            .write_to(&mut buf[..]);
+
+        let pk = Packet::new(&buf[..]);
+        assert_eq!(pk.pid(), 0x123);
+        assert!(pk.payload_unit_start_indicator());
+        assert_eq!(pk.continuity_counter().count(), 7);
+        assert_eq!(pk.adaptation_control(), AdaptationControl::PayloadOnly);
+        assert_eq!(pk.payload().unwrap(), &payload[..]);
+    }
+
+    #[test]
+    fn build_packet_with_adaptation_field_and_payload() {
+        let payload = [0x42u8; 20];
+        let pcr = ClockRef::new(12345, 6);
+        let af = AdaptationFieldBuilder::new()
+            .discontinuity_indicator(true)
+            .pcr(pcr)
+            .splice_countdown(-1);
+        let mut buf = [0u8; PACKET_SIZE];
+        PacketBuilder::new()
+            .pid(0x44)
+            .adaptation_field(af)
+            .payload(&payload[..])
+            .write_to(&mut buf[..]);
+
+        let pk = Packet::new(&buf[..]);
+        assert_eq!(
+            pk.adaptation_control(),
+            AdaptationControl::AdaptationFieldAndPayload
+        );
+        assert_eq!(pk.payload().unwrap(), &payload[..]);
+        let parsed_af = pk.adaptation_field().unwrap();
+        assert!(parsed_af.discontinuity_indicator());
+        assert_eq!(parsed_af.pcr().unwrap(), pcr);
+        assert_eq!(parsed_af.splice_countdown(), Some(-1));
+    }
+
+    #[test]
+    fn build_adaptation_field_only_packet_is_stuffed() {
+        let mut buf = [0u8; PACKET_SIZE];
+        PacketBuilder::new()
+            .pid(0x10)
+            .adaptation_field(AdaptationFieldBuilder::new().random_access_indicator(true))
+            .write_to(&mut buf[..]);
+
+        let pk = Packet::new(&buf[..]);
+        assert_eq!(
+            pk.adaptation_control(),
+            AdaptationControl::AdaptationFieldOnly
+        );
+        assert!(pk.payload().is_none());
+        assert!(pk
+            .adaptation_field()
+            .unwrap()
+            .random_access_indicator()
+            .unwrap());
+        assert_eq!(buf[buf.len() - 1], 0xFF);
+    }
+
+    #[test]
+    fn build_short_payload_synthesizes_stuffing_adaptation_field() {
+        let payload = [0xcdu8; 10];
+        let mut buf = [0u8; PACKET_SIZE];
+        PacketBuilder::new()
+            .pid(0x55)
+            .payload(&payload[..])
+            .write_to(&mut buf[..]);
+
+        let pk = Packet::new(&buf[..]);
+        assert_eq!(
+            pk.adaptation_control(),
+            AdaptationControl::AdaptationFieldAndPayload
+        );
+        assert_eq!(pk.payload().unwrap(), &payload[..]);
+        assert!(!pk.adaptation_field().unwrap().discontinuity_indicator());
+    }
+
+    #[test]
+    fn build_183_byte_payload_uses_zero_length_adaptation_field() {
+        // 183 bytes is the one payload length that does not fit alongside a synthesized
+        // adaptation field carrying its mandatory one-byte flags field, but does fit alongside
+        // the legal `adaptation_field_length == 0` stuffing field, which has no flags byte.
+        let payload = [0xcdu8; PACKET_SIZE - ADAPTATION_FIELD_OFFSET];
+        let mut buf = [0u8; PACKET_SIZE];
+        PacketBuilder::new()
+            .pid(0x55)
+            .payload(&payload[..])
+            .write_to(&mut buf[..]);
+
+        let pk = Packet::new(&buf[..]);
+        assert_eq!(
+            pk.adaptation_control(),
+            AdaptationControl::AdaptationFieldAndPayload
+        );
+        assert_eq!(pk.adaptation_field().unwrap().random_access_indicator(), None);
+        assert_eq!(pk.payload().unwrap(), &payload[..]);
+        // a zero-length adaptation field carries no flags byte, so the indicator reads as unset
+        // rather than panicking on the empty buffer.
+        assert!(!pk.adaptation_field().unwrap().discontinuity_indicator());
+    }
+
+    #[test]
+    fn adaptation_field_truncated() {
+        // pcr_flag is set, but the buffer is too short to hold the 6-byte PCR value, so the
+        // accessor should return None rather than panicking.
+        let buf = [0b0001_0000u8];
+        let af = AdaptationField::new(&buf[..]);
+        assert!(af.pcr().is_none());
+    }
 }
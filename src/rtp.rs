@@ -0,0 +1,210 @@
+//! Support for depayloading MPEG Transport Stream packets carried inside RTP, per
+//! [RFC 2250](https://tools.ietf.org/html/rfc2250) Section 2, so that the resulting bytes can be
+//! fed straight into [`Demultiplex::push()`](../demultiplex/struct.Demultiplex.html).
+
+use packet::PACKET_SIZE;
+
+/// Problems that can occur while interpreting a buffer as an RTP packet carrying MPEG2-TS data.
+#[derive(Eq, PartialEq, Debug)]
+pub enum RtpError {
+    /// the buffer is shorter than the minimum 12-byte fixed RTP header, or shorter than a header
+    /// extension or CSRC list it claims to carry
+    BufferTooShort,
+    /// the RTP `version` field was not `2`, the only version this crate understands
+    UnsupportedVersion(u8),
+    /// RFC 2250 requires the RTP payload to be an integral number of 188-byte TS packets; this
+    /// buffer's payload, of the given length, is not
+    PayloadNotPacketAligned(usize),
+}
+
+/// A borrowed view over a single RTP packet, exposing the fixed header fields needed to strip
+/// RTP framing (including any CSRC list and header extension) and recover the MPEG2-TS payload.
+pub struct RtpPacket<'buf> {
+    buf: &'buf [u8],
+}
+
+const FIXED_HEADER_SIZE: usize = 12;
+
+impl<'buf> RtpPacket<'buf> {
+    /// Wraps `buf` as an RTP packet.  Only the fixed 12-byte header is validated at this point;
+    /// `ts_payload()` performs the remaining validation needed to reach the TS payload.
+    pub fn new(buf: &'buf [u8]) -> Result<RtpPacket<'buf>, RtpError> {
+        if buf.len() < FIXED_HEADER_SIZE {
+            return Err(RtpError::BufferTooShort);
+        }
+        let version = buf[0] >> 6;
+        if version != 2 {
+            return Err(RtpError::UnsupportedVersion(version));
+        }
+        Ok(RtpPacket { buf })
+    }
+
+    fn padding_present(&self) -> bool {
+        self.buf[0] & 0b0010_0000 != 0
+    }
+
+    fn extension_present(&self) -> bool {
+        self.buf[0] & 0b0001_0000 != 0
+    }
+
+    fn csrc_count(&self) -> usize {
+        (self.buf[0] & 0b0000_1111) as usize
+    }
+
+    pub fn marker(&self) -> bool {
+        self.buf[1] & 0b1000_0000 != 0
+    }
+
+    pub fn payload_type(&self) -> u8 {
+        self.buf[1] & 0b0111_1111
+    }
+
+    /// The RTP sequence number, which callers can compare against the value seen on a previous
+    /// packet to detect loss, and signal the resulting discontinuity into the demux's
+    /// continuity-counter handling.
+    pub fn sequence_number(&self) -> u16 {
+        u16::from(self.buf[2]) << 8 | u16::from(self.buf[3])
+    }
+
+    pub fn timestamp(&self) -> u32 {
+        u32::from(self.buf[4]) << 24
+            | u32::from(self.buf[5]) << 16
+            | u32::from(self.buf[6]) << 8
+            | u32::from(self.buf[7])
+    }
+
+    pub fn ssrc(&self) -> u32 {
+        u32::from(self.buf[8]) << 24
+            | u32::from(self.buf[9]) << 16
+            | u32::from(self.buf[10]) << 8
+            | u32::from(self.buf[11])
+    }
+
+    // the offset at which the RTP payload begins, having skipped the fixed header, any CSRC
+    // identifiers, and any header extension
+    fn payload_offset(&self) -> Result<usize, RtpError> {
+        let mut offset = FIXED_HEADER_SIZE + 4 * self.csrc_count();
+        if self.extension_present() {
+            let ext_header = self
+                .buf
+                .get(offset..offset + 4)
+                .ok_or(RtpError::BufferTooShort)?;
+            let ext_len_words = u16::from(ext_header[2]) << 8 | u16::from(ext_header[3]);
+            offset += 4 + 4 * ext_len_words as usize;
+        }
+        Ok(offset)
+    }
+
+    /// The MPEG2-TS payload carried by this RTP packet: an integral number of `PACKET_SIZE`-byte
+    /// TS packets, per RFC 2250 Section 2, with any trailing RTP padding removed.
+    pub fn ts_payload(&self) -> Result<&'buf [u8], RtpError> {
+        let start = self.payload_offset()?;
+        let mut end = self.buf.len();
+        if self.padding_present() {
+            let pad_len = *self.buf.last().ok_or(RtpError::BufferTooShort)? as usize;
+            end = end.checked_sub(pad_len).ok_or(RtpError::BufferTooShort)?;
+        }
+        let payload = self.buf.get(start..end).ok_or(RtpError::BufferTooShort)?;
+        if payload.len() % PACKET_SIZE != 0 {
+            return Err(RtpError::PayloadNotPacketAligned(payload.len()));
+        }
+        Ok(payload)
+    }
+}
+
+/// Depayloads a sequence of RTP packets carrying MPEG2-TS, tracking the RTP sequence number
+/// across calls so that callers can be told when packets were lost between one call and the
+/// next.
+///
+/// `depacketize()` only reports the loss as a `bool`; it does not itself signal the resulting
+/// discontinuity into `Demultiplex`'s continuity-counter handling; callers act on the returned
+/// flag themselves.
+#[derive(Default)]
+pub struct RtpDepacketizer {
+    last_sequence_number: Option<u16>,
+}
+
+impl RtpDepacketizer {
+    pub fn new() -> RtpDepacketizer {
+        Default::default()
+    }
+
+    /// Parses `buf` as a single RTP packet and returns its MPEG2-TS payload, ready to be passed
+    /// to `Demultiplex::push()`, along with whether a gap was detected in the RTP sequence number
+    /// since the previous call to `depacketize()`.
+    pub fn depacketize<'buf>(&mut self, buf: &'buf [u8]) -> Result<(&'buf [u8], bool), RtpError> {
+        let rtp = RtpPacket::new(buf)?;
+        let seq = rtp.sequence_number();
+        let lost = self
+            .last_sequence_number
+            .is_some_and(|last| seq != last.wrapping_add(1));
+        self.last_sequence_number = Some(seq);
+        Ok((rtp.ts_payload()?, lost))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn rtp_packet(seq: u16, ts_packet_count: usize) -> Vec<u8> {
+        let mut buf = vec![0u8; FIXED_HEADER_SIZE + ts_packet_count * PACKET_SIZE];
+        buf[0] = 0b1000_0000; // version 2, no padding, no extension, no CSRC
+        buf[1] = 33; // payload type MP2T
+        buf[2] = (seq >> 8) as u8;
+        buf[3] = seq as u8;
+        for i in 0..ts_packet_count {
+            buf[FIXED_HEADER_SIZE + i * PACKET_SIZE] = ::packet::SYNC_BYTE;
+        }
+        buf
+    }
+
+    #[test]
+    fn depacketize_sequential() {
+        let mut d = RtpDepacketizer::new();
+        let first = rtp_packet(1, 2);
+        let (payload, lost) = d.depacketize(&first).unwrap();
+        assert!(!lost);
+        assert_eq!(payload.len(), 2 * PACKET_SIZE);
+
+        let second = rtp_packet(2, 1);
+        let (_, lost) = d.depacketize(&second).unwrap();
+        assert!(!lost);
+    }
+
+    #[test]
+    fn depacketize_detects_loss() {
+        let mut d = RtpDepacketizer::new();
+        d.depacketize(&rtp_packet(1, 1)).unwrap();
+        let (_, lost) = d.depacketize(&rtp_packet(3, 1)).unwrap();
+        assert!(lost);
+    }
+
+    #[test]
+    fn sequence_number_wraps() {
+        let mut d = RtpDepacketizer::new();
+        d.depacketize(&rtp_packet(0xffff, 1)).unwrap();
+        let (_, lost) = d.depacketize(&rtp_packet(0, 1)).unwrap();
+        assert!(!lost);
+    }
+
+    #[test]
+    fn rejects_misaligned_payload() {
+        let mut buf = rtp_packet(1, 1);
+        buf.pop();
+        let mut d = RtpDepacketizer::new();
+        assert_eq!(
+            d.depacketize(&buf[..]),
+            Err(RtpError::PayloadNotPacketAligned(PACKET_SIZE - 1))
+        );
+    }
+
+    #[test]
+    fn rejects_short_buffer() {
+        let buf = [0u8; 4];
+        match RtpPacket::new(&buf[..]) {
+            Err(RtpError::BufferTooShort) => (),
+            other => panic!("expected BufferTooShort, got {:?}", other.map(|_| ())),
+        }
+    }
+}